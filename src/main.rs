@@ -7,16 +7,246 @@ use crossterm::{
 };
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io::{stdout, Write};
+use std::io::{stdin, stdout, IsTerminal, Write};
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use clap::{Arg, Command};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::{SyntaxReference, SyntaxSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+enum TaskStatus {
+    Inbox,
+    #[default]
+    Pending,
+    Active,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize)]
 struct Task {
     id: usize,
     title: String,
     description: String,
-    completed: bool,
+    status: TaskStatus,
+    // The backing Taskwarrior task's uuid, when this task came from the
+    // Taskwarrior backend. Unused (and omitted) by the native JSON backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_id: Option<String>,
+}
+
+// Older task files only know `completed: bool`. Deserialize through a raw
+// shape so a missing `status` field falls back to that flag instead of
+// losing whether the task was done.
+impl<'de> Deserialize<'de> for Task {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct TaskRaw {
+            id: usize,
+            title: String,
+            description: String,
+            #[serde(default)]
+            status: Option<TaskStatus>,
+            #[serde(default)]
+            completed: Option<bool>,
+            #[serde(default)]
+            external_id: Option<String>,
+        }
+
+        let raw = TaskRaw::deserialize(deserializer)?;
+        let status = raw.status.unwrap_or(match raw.completed {
+            Some(true) => TaskStatus::Done,
+            _ => TaskStatus::Pending,
+        });
+
+        Ok(Task {
+            id: raw.id,
+            title: raw.title,
+            description: raw.description,
+            status,
+            external_id: raw.external_id,
+        })
+    }
+}
+
+// Backend-agnostic persistence so the rest of the app doesn't care whether
+// tasks live in the native JSON file or are proxied through Taskwarrior.
+trait Store: std::fmt::Debug {
+    fn load(&self) -> Vec<Task>;
+    fn save(&self, tasks: &[Task]);
+    fn add(&self, task: &mut Task);
+    fn update(&self, task: &Task);
+    fn delete(&self, task: &Task);
+}
+
+#[derive(Debug)]
+struct JsonStore {
+    path: PathBuf,
+}
+
+impl JsonStore {
+    fn new() -> Self {
+        Self { path: App::get_data_file_path() }
+    }
+}
+
+impl Store for JsonStore {
+    fn load(&self) -> Vec<Task> {
+        // Check if there's an old tasks.json in current directory to migrate
+        let old_file = PathBuf::from("tasks.json");
+        if old_file.exists() && !self.path.exists() {
+            if let Ok(data) = fs::read_to_string(&old_file) {
+                if fs::write(&self.path, &data).is_ok() {
+                    let _ = fs::remove_file(&old_file);
+                    eprintln!("Migrated tasks from ./tasks.json to {}", self.path.display());
+                }
+            }
+        }
+
+        if let Ok(data) = fs::read_to_string(&self.path) {
+            if let Ok(tasks) = serde_json::from_str::<Vec<Task>>(&data) {
+                return tasks;
+            }
+        }
+        Vec::new()
+    }
+
+    fn save(&self, tasks: &[Task]) {
+        if let Ok(data) = serde_json::to_string_pretty(tasks) {
+            let _ = fs::write(&self.path, data);
+        }
+    }
+
+    // The JSON backend always persists the whole vector through `save`, so
+    // individual adds/updates/deletes don't need their own round trip.
+    fn add(&self, _task: &mut Task) {}
+    fn update(&self, _task: &Task) {}
+    fn delete(&self, _task: &Task) {}
+}
+
+#[derive(Debug)]
+struct TaskwarriorStore;
+
+impl Store for TaskwarriorStore {
+    fn load(&self) -> Vec<Task> {
+        let output = std::process::Command::new("task").arg("export").output();
+        let Ok(output) = output else {
+            return Vec::new();
+        };
+        let Ok(items) = serde_json::from_slice::<Vec<serde_json::Value>>(&output.stdout) else {
+            return Vec::new();
+        };
+
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let title = item
+                    .get("description")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let description = item
+                    .get("annotations")
+                    .and_then(|v| v.as_array())
+                    .map(|annotations| {
+                        annotations
+                            .iter()
+                            .filter_map(|a| a.get("description").and_then(|d| d.as_str()))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    })
+                    .unwrap_or_default();
+                let status = match item.get("status").and_then(|v| v.as_str()) {
+                    Some("completed") => TaskStatus::Done,
+                    Some("pending") if item.get("start").is_some() => TaskStatus::Active,
+                    Some("pending") => TaskStatus::Pending,
+                    _ => TaskStatus::Inbox,
+                };
+                let external_id = item.get("uuid").and_then(|v| v.as_str()).map(str::to_string);
+
+                Task { id: i + 1, title, description, status, external_id }
+            })
+            .collect()
+    }
+
+    // Taskwarrior is the source of truth; per-task changes already went out
+    // through `add`/`update`, so there's nothing left to bulk-persist here.
+    fn save(&self, _tasks: &[Task]) {}
+
+    fn add(&self, task: &mut Task) {
+        let output = std::process::Command::new("task").arg("add").arg(&task.title).output();
+        let Ok(output) = output else {
+            return;
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(id) = stdout
+            .split_whitespace()
+            .find_map(|word| word.trim_end_matches('.').parse::<u32>().ok())
+        else {
+            return;
+        };
+
+        if !task.description.is_empty() {
+            let _ = std::process::Command::new("task")
+                .arg(id.to_string())
+                .arg("annotate")
+                .arg(&task.description)
+                .status();
+        }
+        let uuid = std::process::Command::new("task")
+            .arg("_get")
+            .arg(format!("{}.uuid", id))
+            .output()
+            .ok()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .filter(|u| !u.is_empty());
+        task.external_id = Some(uuid.unwrap_or_else(|| id.to_string()));
+    }
+
+    fn update(&self, task: &Task) {
+        let Some(id) = &task.external_id else {
+            return;
+        };
+        let _ = std::process::Command::new("task")
+            .arg(id)
+            .arg("modify")
+            .arg(&task.title)
+            .status();
+        // `stop` only clears the `start` attribute (time tracking); it does
+        // not revert a completed task, so un-completing needs an explicit
+        // `modify status:pending` instead.
+        match task.status {
+            TaskStatus::Done => {
+                let _ = std::process::Command::new("task").arg(id).arg("done").status();
+            }
+            TaskStatus::Active => {
+                let _ = std::process::Command::new("task").arg(id).arg("start").status();
+            }
+            TaskStatus::Pending | TaskStatus::Inbox => {
+                let _ = std::process::Command::new("task")
+                    .arg(id)
+                    .arg("modify")
+                    .arg("status:pending")
+                    .status();
+            }
+        }
+    }
+
+    fn delete(&self, task: &Task) {
+        let Some(id) = &task.external_id else {
+            return;
+        };
+        let _ = std::process::Command::new("task")
+            .arg(id)
+            .arg("delete")
+            .arg("rc.confirmation=no")
+            .status();
+    }
 }
 
 #[derive(Debug)]
@@ -26,6 +256,14 @@ struct App {
     mode: AppMode,
     input_buffer: String,
     next_id: usize,
+    operations: Vec<Op>,
+    status_message: Option<String>,
+    // Indices into `tasks` that match the live filter query, in match-score
+    // order. Kept as an index map (not a clone of the tasks) so actions still
+    // operate on the real `Task`.
+    visible: Vec<usize>,
+    filter_active: bool,
+    store: Box<dyn Store>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -33,18 +271,65 @@ enum AppMode {
     Normal,
     AddTask,
     EditTask,
-    AddDescription,
-    EditDescription,
+    Filter,
+}
+
+// Which persistence backend a run of the app is using. `Json` is the
+// long-standing native format; `Taskwarrior` shells out to the `task` CLI so
+// existing Taskwarrior users can drive their task list through this UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Json,
+    Taskwarrior,
+}
+
+// A reversible record of a mutation, pushed by whichever App method performs
+// it so `undo` can pop the stack and invert the change.
+#[derive(Debug, Clone)]
+enum Op {
+    Deleted { task: Task, index: usize },
+    Toggled { id: usize },
+    Edited { id: usize, old_title: String },
+    StatusChanged { id: usize, old_status: TaskStatus },
+    EditedDescription { id: usize, old_description: String },
+}
+
+// Every state transition the app can make, decoupled from the keys that
+// trigger it. `handle_input` maps key presses to `Msg`s; `--exec` and piped
+// stdin map lines of text to the same `Msg`s, so both drive identical code.
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    FocusNext,
+    FocusPrevious,
+    ToggleStatus,
+    StartTask,
+    StopTask,
+    InboxTask,
+    Undo,
+    AddTask(String),
+    EditTitle(String),
+    EditDescription(String),
+    DeleteTask,
+    Quit,
 }
 
 impl App {
-    fn new() -> Self {
+    fn new(backend: Backend) -> Self {
+        let store: Box<dyn Store> = match backend {
+            Backend::Json => Box::new(JsonStore::new()),
+            Backend::Taskwarrior => Box::new(TaskwarriorStore),
+        };
         let mut app = App {
             tasks: Vec::new(),
             selected_index: 0,
             mode: AppMode::Normal,
             input_buffer: String::new(),
             next_id: 1,
+            operations: Vec::new(),
+            status_message: None,
+            visible: Vec::new(),
+            filter_active: false,
+            store,
         };
         app.load_tasks();
         app
@@ -66,56 +351,60 @@ impl App {
     }
 
     fn load_tasks(&mut self) {
-        let data_file = Self::get_data_file_path();
-        
-        // Check if there's an old tasks.json in current directory to migrate
-        let old_file = PathBuf::from("tasks.json");
-        if old_file.exists() && !data_file.exists() {
-            if let Ok(data) = fs::read_to_string(&old_file) {
-                // Try to write to new location
-                if let Ok(_) = fs::write(&data_file, &data) {
-                    // Successfully migrated, remove old file
-                    let _ = fs::remove_file(&old_file);
-                    eprintln!("Migrated tasks from ./tasks.json to {}", data_file.display());
-                }
-            }
-        }
-        
-        if data_file.exists() {
-            if let Ok(data) = fs::read_to_string(&data_file) {
-                if let Ok(tasks) = serde_json::from_str::<Vec<Task>>(&data) {
-                    self.tasks = tasks;
-                    if let Some(max_id) = self.tasks.iter().map(|t| t.id).max() {
-                        self.next_id = max_id + 1;
-                    }
-                }
-            }
+        self.tasks = self.store.load();
+        if let Some(max_id) = self.tasks.iter().map(|t| t.id).max() {
+            self.next_id = max_id + 1;
         }
     }
 
     fn save_tasks(&self) {
-        let data_file = Self::get_data_file_path();
-        if let Ok(data) = serde_json::to_string_pretty(&self.tasks) {
-            let _ = fs::write(&data_file, data);
-        }
+        self.store.save(&self.tasks);
     }
 
     fn add_task(&mut self, title: String, description: String) {
-        let task = Task {
+        self.status_message = None;
+        let mut task = Task {
             id: self.next_id,
             title,
             description,
-            completed: false,
+            status: TaskStatus::Pending,
+            external_id: None,
         };
+        self.store.add(&mut task);
         self.tasks.push(task);
         self.next_id += 1;
         self.save_tasks();
     }
 
+    // Resolves `selected_index` to a real index into `tasks`, going through
+    // the filtered `visible` map while a filter is active.
+    fn current_task_index(&self) -> Option<usize> {
+        if self.filter_active {
+            self.visible.get(self.selected_index).copied()
+        } else if self.selected_index < self.tasks.len() {
+            Some(self.selected_index)
+        } else {
+            None
+        }
+    }
+
     fn delete_task(&mut self) {
-        if !self.tasks.is_empty() && self.selected_index < self.tasks.len() {
-            self.tasks.remove(self.selected_index);
-            if self.selected_index >= self.tasks.len() && !self.tasks.is_empty() {
+        self.status_message = None;
+        if let Some(index) = self.current_task_index() {
+            let task = self.tasks.remove(index);
+            self.store.delete(&task);
+            self.operations.push(Op::Deleted { task, index });
+            if self.filter_active {
+                self.visible.retain(|&i| i != index);
+                for v in self.visible.iter_mut() {
+                    if *v > index {
+                        *v -= 1;
+                    }
+                }
+                if self.selected_index >= self.visible.len() {
+                    self.selected_index = self.visible.len().saturating_sub(1);
+                }
+            } else if self.selected_index >= self.tasks.len() && !self.tasks.is_empty() {
                 self.selected_index = self.tasks.len() - 1;
             }
             self.save_tasks();
@@ -123,39 +412,330 @@ impl App {
     }
 
     fn toggle_task(&mut self) {
-        if !self.tasks.is_empty() && self.selected_index < self.tasks.len() {
-            self.tasks[self.selected_index].completed = !self.tasks[self.selected_index].completed;
+        self.status_message = None;
+        if let Some(index) = self.current_task_index() {
+            let id = self.tasks[index].id;
+            let status = &mut self.tasks[index].status;
+            *status = if *status == TaskStatus::Done {
+                TaskStatus::Pending
+            } else {
+                TaskStatus::Done
+            };
+            self.operations.push(Op::Toggled { id });
+            self.store.update(&self.tasks[index]);
+            self.save_tasks();
+        }
+    }
+
+    fn start_task(&mut self) {
+        if let Some(index) = self.current_task_index() {
+            let id = self.tasks[index].id;
+            let old_status = self.tasks[index].status;
+            self.tasks[index].status = TaskStatus::Active;
+            self.operations.push(Op::StatusChanged { id, old_status });
+            self.store.update(&self.tasks[index]);
+            self.save_tasks();
+        }
+    }
+
+    fn stop_task(&mut self) {
+        if let Some(index) = self.current_task_index() {
+            let id = self.tasks[index].id;
+            let old_status = self.tasks[index].status;
+            self.tasks[index].status = TaskStatus::Pending;
+            self.operations.push(Op::StatusChanged { id, old_status });
+            self.store.update(&self.tasks[index]);
+            self.save_tasks();
+        }
+    }
+
+    fn inbox_task(&mut self) {
+        if let Some(index) = self.current_task_index() {
+            let id = self.tasks[index].id;
+            let old_status = self.tasks[index].status;
+            self.tasks[index].status = TaskStatus::Inbox;
+            self.operations.push(Op::StatusChanged { id, old_status });
+            self.store.update(&self.tasks[index]);
             self.save_tasks();
         }
     }
 
     fn edit_current_task(&mut self, title: String) {
-        if !self.tasks.is_empty() && self.selected_index < self.tasks.len() {
-            self.tasks[self.selected_index].title = title;
+        self.status_message = None;
+        if let Some(index) = self.current_task_index() {
+            let id = self.tasks[index].id;
+            let old_title = self.tasks[index].title.clone();
+            self.tasks[index].title = title;
+            self.operations.push(Op::Edited { id, old_title });
+            self.store.update(&self.tasks[index]);
             self.save_tasks();
         }
     }
 
     fn edit_current_description(&mut self, description: String) {
-        if !self.tasks.is_empty() && self.selected_index < self.tasks.len() {
-            self.tasks[self.selected_index].description = description;
+        self.status_message = None;
+        if let Some(index) = self.current_task_index() {
+            let id = self.tasks[index].id;
+            let old_description = self.tasks[index].description.clone();
+            self.tasks[index].description = description;
+            self.operations.push(Op::EditedDescription { id, old_description });
+            self.store.update(&self.tasks[index]);
             self.save_tasks();
         }
     }
 
+    // Scores every task as a fuzzy subsequence match against the live query
+    // and keeps only the matches, best-scored first, as an index map into
+    // `tasks`.
+    fn refresh_filter(&mut self) {
+        let query = self.input_buffer.to_lowercase();
+        let mut scored: Vec<(usize, i32)> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| {
+                let haystack = format!("{} {}", t.title, t.description).to_lowercase();
+                fuzzy_score(&haystack, &query).map(|score| (i, score))
+            })
+            .collect();
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        self.visible = scored.into_iter().map(|(i, _)| i).collect();
+        if self.selected_index >= self.visible.len() {
+            self.selected_index = self.visible.len().saturating_sub(1);
+        }
+    }
+
+    fn clear_filter(&mut self) {
+        self.filter_active = false;
+        self.visible.clear();
+        self.input_buffer.clear();
+        self.mode = AppMode::Normal;
+        if self.selected_index >= self.tasks.len() {
+            self.selected_index = self.tasks.len().saturating_sub(1);
+        }
+    }
+
+    // Pops the last recorded `Op` and applies its inverse, turning destructive
+    // actions like delete into a safe, undoable step without an external trash.
+    fn undo(&mut self) {
+        match self.operations.pop() {
+            Some(Op::Deleted { mut task, index }) => {
+                let index = index.min(self.tasks.len());
+                // The backing store already dropped the old copy on delete, so
+                // restoring it means re-adding it rather than just updating.
+                self.store.add(&mut task);
+                self.tasks.insert(index, task);
+                self.selected_index = index;
+                self.status_message = Some("Undo: restored deleted task".to_string());
+            }
+            Some(Op::Toggled { id }) => {
+                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                    task.status = if task.status == TaskStatus::Done {
+                        TaskStatus::Pending
+                    } else {
+                        TaskStatus::Done
+                    };
+                    self.store.update(task);
+                }
+                self.status_message = Some("Undo: reverted toggle".to_string());
+            }
+            Some(Op::Edited { id, old_title }) => {
+                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                    task.title = old_title;
+                    self.store.update(task);
+                }
+                self.status_message = Some("Undo: restored previous title".to_string());
+            }
+            Some(Op::StatusChanged { id, old_status }) => {
+                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                    task.status = old_status;
+                    self.store.update(task);
+                }
+                self.status_message = Some("Undo: restored previous status".to_string());
+            }
+            Some(Op::EditedDescription { id, old_description }) => {
+                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                    task.description = old_description;
+                    self.store.update(task);
+                }
+                self.status_message = Some("Undo: restored previous description".to_string());
+            }
+            None => {
+                self.status_message = Some("Nothing to undo".to_string());
+            }
+        }
+        // The ops above mutate `tasks` directly, so the filtered index map
+        // (if a filter is applied) needs to be recomputed to match.
+        if self.filter_active {
+            self.refresh_filter();
+        }
+        self.save_tasks();
+    }
+
+    // Applies one `Msg`. Key handling and `--exec`/piped scripting both funnel
+    // into this, so a given `Msg` runs the same code from either side. Adding
+    // a task together with an editor-written description, and filter state,
+    // are handled in `handle_input` instead. Returns `false` to stop the app.
+    fn handle_msg(&mut self, msg: Msg) -> bool {
+        match msg {
+            Msg::FocusNext => self.move_down(),
+            Msg::FocusPrevious => self.move_up(),
+            Msg::ToggleStatus => self.toggle_task(),
+            Msg::StartTask => self.start_task(),
+            Msg::StopTask => self.stop_task(),
+            Msg::InboxTask => self.inbox_task(),
+            Msg::Undo => self.undo(),
+            Msg::AddTask(title) => self.add_task(title, String::new()),
+            Msg::EditTitle(title) => self.edit_current_task(title),
+            Msg::EditDescription(description) => self.edit_current_description(description),
+            Msg::DeleteTask => self.delete_task(),
+            Msg::Quit => return false,
+        }
+        true
+    }
+
+    // Suspends the TUI and hands the description off to $EDITOR so it can be
+    // written as real multi-line text instead of a single input_buffer line.
+    fn edit_in_editor(&mut self, initial: &str) -> std::io::Result<Option<String>> {
+        terminal::disable_raw_mode()?;
+        execute!(stdout(), terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+        let tmp_path = Self::get_data_file_path()
+            .with_file_name(format!(".rtasks-description-{}.tmp", self.next_id));
+        fs::write(&tmp_path, initial)?;
+
+        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+        std::process::Command::new(editor).arg(&tmp_path).status()?;
+
+        let edited = fs::read_to_string(&tmp_path).unwrap_or_default();
+        let _ = fs::remove_file(&tmp_path);
+
+        terminal::enable_raw_mode()?;
+
+        let trimmed = edited.trim();
+        if trimmed.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(trimmed.to_string()))
+        }
+    }
+
     fn move_up(&mut self) {
-        if !self.tasks.is_empty() && self.selected_index > 0 {
+        if self.selected_index > 0 {
             self.selected_index -= 1;
         }
     }
 
     fn move_down(&mut self) {
-        if !self.tasks.is_empty() && self.selected_index < self.tasks.len() - 1 {
+        let len = if self.filter_active { self.visible.len() } else { self.tasks.len() };
+        if self.selected_index + 1 < len {
             self.selected_index += 1;
         }
     }
 }
 
+// Subsequence fuzzy match: every character of `query` must appear in order in
+// `haystack`. Characters are compared exactly, so callers lowercase both sides
+// for a case-insensitive match. Consecutive matches score progressively
+// higher than scattered ones so tighter matches sort first.
+fn fuzzy_score(haystack: &str, query: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let hay: Vec<char> = haystack.chars().collect();
+    let mut hay_idx = 0;
+    let mut run = 0;
+    let mut score = 0;
+    for qc in query.chars() {
+        let mut found = false;
+        while hay_idx < hay.len() {
+            let hc = hay[hay_idx];
+            hay_idx += 1;
+            if hc == qc {
+                run += 1;
+                score += run;
+                found = true;
+                break;
+            } else {
+                run = 0;
+            }
+        }
+        if !found {
+            return None;
+        }
+    }
+    Some(score)
+}
+
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+
+fn syntax_set() -> &'static SyntaxSet {
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
+// Looks for a fenced-code-block hint (```rust) or a `#lang:rust` marker on the
+// description's first line to decide how to highlight the preview pane.
+fn detect_syntax(description: &str) -> Option<&'static SyntaxReference> {
+    let first_line = description.lines().next()?;
+    let hint = first_line
+        .strip_prefix("```")
+        .or_else(|| first_line.strip_prefix("#lang:"))?
+        .trim();
+    if hint.is_empty() {
+        return None;
+    }
+    syntax_set().find_syntax_by_token(hint)
+}
+
+// Renders `description` into the preview pane, syntax-highlighting it when a
+// language hint is present and falling back to plain dark-grey text otherwise.
+fn draw_preview_pane(description: &str, x: u16, y: u16, width: u16, height: u16) -> std::io::Result<()> {
+    let width = width as usize;
+    match detect_syntax(description) {
+        Some(syntax) => {
+            let theme = &theme_set().themes["base16-ocean.dark"];
+            let mut highlighter = HighlightLines::new(syntax, theme);
+            let body = description.lines().skip(1).take(height as usize);
+            for (row, line) in body.enumerate() {
+                execute!(stdout(), cursor::MoveTo(x, y + row as u16))?;
+                let mut printed = 0;
+                if let Ok(ranges) = highlighter.highlight_line(line, syntax_set()) {
+                    for (style, text) in ranges {
+                        if printed >= width {
+                            break;
+                        }
+                        let remaining = width - printed;
+                        let clipped: String = text.chars().take(remaining).collect();
+                        printed += clipped.chars().count();
+                        let fg = style.foreground;
+                        execute!(
+                            stdout(),
+                            SetForegroundColor(Color::Rgb { r: fg.r, g: fg.g, b: fg.b }),
+                            Print(clipped)
+                        )?;
+                    }
+                }
+                execute!(stdout(), ResetColor)?;
+            }
+        }
+        None => {
+            execute!(stdout(), SetForegroundColor(Color::DarkGrey))?;
+            for (row, line) in description.lines().take(height as usize).enumerate() {
+                execute!(stdout(), cursor::MoveTo(x, y + row as u16))?;
+                let clipped: String = line.chars().take(width).collect();
+                execute!(stdout(), Print(clipped))?;
+            }
+            execute!(stdout(), ResetColor)?;
+        }
+    }
+    Ok(())
+}
+
 fn draw_ui(app: &App) -> std::io::Result<()> {
     execute!(stdout(), terminal::Clear(ClearType::All))?;
     
@@ -179,13 +759,17 @@ fn draw_ui(app: &App) -> std::io::Result<()> {
     let content_end = rows.saturating_sub(2);
     let mut current_line = content_start;
 
+    // Split the content area into a left task list and a right preview pane.
+    let list_width = (cols / 2).max(20);
+    let preview_x = list_width + 1;
+    let preview_width = cols.saturating_sub(preview_x);
+
     // Instructions/input area based on mode
     if app.mode != AppMode::Normal {
         let instructions = match app.mode {
             AppMode::AddTask => "Adding new task. Type title and press Enter (Esc to cancel):",
             AppMode::EditTask => "Editing task title. Type new title and press Enter (Esc to cancel):",
-            AppMode::AddDescription => "Adding description. Type description and press Enter (Esc to cancel):",
-            AppMode::EditDescription => "Editing description. Type new description and press Enter (Esc to cancel):",
+            AppMode::Filter => "Filter: type to narrow the list (Enter to keep, Esc to clear):",
             _ => "",
         };
         
@@ -209,59 +793,120 @@ fn draw_ui(app: &App) -> std::io::Result<()> {
         current_line += 2;
     }
 
-    // Task list
-    if app.tasks.is_empty() {
+    // Task list: the full vector, or the filtered `visible` index map.
+    let row_indices: Vec<usize> = if app.filter_active {
+        app.visible.clone()
+    } else {
+        (0..app.tasks.len()).collect()
+    };
+
+    if row_indices.is_empty() {
+        let message = if app.filter_active {
+            "No tasks match the filter."
+        } else {
+            "No tasks yet. Press 'A' to add your first task!"
+        };
         execute!(
             stdout(),
             cursor::MoveTo(0, current_line),
             SetForegroundColor(Color::DarkGrey),
-            Print("No tasks yet. Press 'A' to add your first task!"),
+            Print(message),
             ResetColor
         )?;
     } else {
-        for (index, task) in app.tasks.iter().enumerate() {
+        for (index, &task_idx) in row_indices.iter().enumerate() {
             // Stop drawing if we've reached the bottom of content area
             if current_line >= content_end {
                 break;
             }
-            
-            let is_selected = index == app.selected_index && app.mode == AppMode::Normal;
-            let status_symbol = if task.completed { "[X]" } else { "[ ]" };
-            
+
+            let task = &app.tasks[task_idx];
+            let is_selected = index == app.selected_index
+                && (app.mode == AppMode::Normal || app.mode == AppMode::Filter);
+            let status_symbol = match task.status {
+                TaskStatus::Inbox => "[>]",
+                TaskStatus::Pending => "[ ]",
+                TaskStatus::Active => "[~]",
+                TaskStatus::Done => "[X]",
+            };
+
             execute!(stdout(), cursor::MoveTo(0, current_line))?;
-            
+
             if is_selected {
                 execute!(stdout(), SetForegroundColor(Color::Black))?;
                 execute!(stdout(), SetBackgroundColor(Color::White))?;
-            } else if task.completed {
-                execute!(stdout(), SetForegroundColor(Color::DarkGrey))?;
             } else {
-                execute!(stdout(), SetForegroundColor(Color::White))?;
+                match task.status {
+                    TaskStatus::Done => execute!(stdout(), SetForegroundColor(Color::DarkGrey))?,
+                    TaskStatus::Active => execute!(stdout(), SetForegroundColor(Color::Yellow))?,
+                    TaskStatus::Inbox => execute!(stdout(), SetForegroundColor(Color::Cyan))?,
+                    TaskStatus::Pending => execute!(stdout(), SetForegroundColor(Color::White))?,
+                }
             }
 
+            let budget = list_width as usize;
             let task_text = format!("{} {} {}", status_symbol, task.id, task.title);
-            execute!(stdout(), Print(task_text))?;
-            
-            if !task.description.is_empty() {
+            let task_text_clipped: String = task_text.chars().take(budget).collect();
+            let printed = task_text_clipped.chars().count();
+            execute!(stdout(), Print(task_text_clipped))?;
+
+            let remaining = budget.saturating_sub(printed);
+            if !task.description.is_empty() && remaining > 3 {
                 execute!(stdout(), SetForegroundColor(Color::DarkGrey))?;
-                execute!(stdout(), Print(format!(" - {}", task.description)))?;
+                let first_line = task.description.lines().next().unwrap_or("");
+                let more = if task.description.contains('\n') { " [...]" } else { "" };
+                let desc_text = format!(" - {}{}", first_line, more);
+                let desc_clipped: String = desc_text.chars().take(remaining).collect();
+                execute!(stdout(), Print(desc_clipped))?;
             }
-            
+
             execute!(stdout(), SetBackgroundColor(Color::Reset))?;
             execute!(stdout(), ResetColor)?;
             current_line += 1;
         }
     }
 
+    // Divider and preview pane for the selected task's full description.
+    for y in content_start..content_end {
+        execute!(
+            stdout(),
+            cursor::MoveTo(list_width, y),
+            SetForegroundColor(Color::DarkGrey),
+            Print("\u{2502}")
+        )?;
+    }
+    execute!(stdout(), ResetColor)?;
+
+    if let Some(task) = app.current_task_index().and_then(|idx| app.tasks.get(idx)) {
+        execute!(
+            stdout(),
+            cursor::MoveTo(preview_x, content_start),
+            SetForegroundColor(Color::Yellow),
+            Print(format!("{} (preview)", task.title.chars().take(preview_width as usize).collect::<String>())),
+            ResetColor
+        )?;
+        draw_preview_pane(
+            &task.description,
+            preview_x,
+            content_start + 1,
+            preview_width,
+            content_end.saturating_sub(content_start + 1),
+        )?;
+    }
+
     // Draw bottom status bar (vim-like)
     execute!(stdout(), cursor::MoveTo(0, rows - 1))?;
     execute!(stdout(), SetBackgroundColor(Color::DarkBlue))?;
     execute!(stdout(), SetForegroundColor(Color::White))?;
     
-    let status_text = if app.mode == AppMode::Normal {
-        " Controls: â†‘â†“ Navigate | Space: Toggle | A: Add | E: Edit | D: Edit Desc | Del: Delete | Q: Quit"
+    let status_text = if let Some(message) = &app.status_message {
+        format!(" {}", message)
+    } else if app.mode == AppMode::Normal && app.filter_active {
+        format!(" Filter: \"{}\" ({} match{}) | Esc: clear filter", app.input_buffer, app.visible.len(), if app.visible.len() == 1 { "" } else { "es" })
+    } else if app.mode == AppMode::Normal {
+        " Controls: â†‘â†“ Navigate | Space: Toggle | S: Start | P: Pause | I: Inbox | A: Add | E: Edit | D: Edit Desc | U: Undo | /: Filter | Del: Delete | Q: Quit".to_string()
     } else {
-        " Press Enter to confirm | Esc to cancel"
+        " Press Enter to confirm | Esc to cancel".to_string()
     };
     
     let status_padding = " ".repeat((cols as usize).saturating_sub(status_text.len()));
@@ -274,93 +919,165 @@ fn draw_ui(app: &App) -> std::io::Result<()> {
     Ok(())
 }
 
+// Key-to-`Msg` translator. Logic kept here rather than in a `Msg`: entering
+// text-input mode, the filter mode and its live query, suspending the
+// terminal to shell out to $EDITOR, and adding a task with the description
+// written there (via `add_task` directly, as `Msg::AddTask` carries only a
+// title).
 fn handle_input(app: &mut App) -> std::io::Result<bool> {
     if let Event::Key(KeyEvent { code, modifiers, .. }) = event::read()? {
         match app.mode {
-            AppMode::Normal => match code {
-                KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(false),
+            AppMode::Normal => {
+                let msg = match code {
+                    KeyCode::Char('q') | KeyCode::Char('Q') => Some(Msg::Quit),
+                    KeyCode::Up => Some(Msg::FocusPrevious),
+                    KeyCode::Down => Some(Msg::FocusNext),
+                    KeyCode::Char(' ') => Some(Msg::ToggleStatus),
+                    KeyCode::Char('s') | KeyCode::Char('S') => Some(Msg::StartTask),
+                    KeyCode::Char('p') | KeyCode::Char('P') => Some(Msg::StopTask),
+                    KeyCode::Char('i') | KeyCode::Char('I') => Some(Msg::InboxTask),
+                    KeyCode::Char('u') | KeyCode::Char('U') => Some(Msg::Undo),
+                    KeyCode::Delete => Some(Msg::DeleteTask),
+                    KeyCode::Esc if app.filter_active => {
+                        app.clear_filter();
+                        None
+                    }
+                    KeyCode::Char('/') => {
+                        app.mode = AppMode::Filter;
+                        app.input_buffer.clear();
+                        app.filter_active = true;
+                        app.selected_index = 0;
+                        app.refresh_filter();
+                        None
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        app.filter_active = false;
+                        app.mode = AppMode::AddTask;
+                        app.input_buffer.clear();
+                        None
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') => {
+                        if let Some(index) = app.current_task_index() {
+                            let title = app.tasks[index].title.clone();
+                            app.selected_index = index;
+                            app.filter_active = false;
+                            app.mode = AppMode::EditTask;
+                            app.input_buffer = title;
+                        }
+                        None
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') => {
+                        if let Some(index) = app.current_task_index() {
+                            let initial = app.tasks[index].description.clone();
+                            if let Some(description) = app.edit_in_editor(&initial)? {
+                                return Ok(app.handle_msg(Msg::EditDescription(description)));
+                            }
+                        }
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(msg) = msg {
+                    return Ok(app.handle_msg(msg));
+                }
+            }
+            AppMode::Filter => match code {
+                KeyCode::Esc => {
+                    app.clear_filter();
+                }
+                KeyCode::Enter => {
+                    // Keep the filter active but return to Normal mode.
+                    app.mode = AppMode::Normal;
+                }
                 KeyCode::Up => app.move_up(),
                 KeyCode::Down => app.move_down(),
-                KeyCode::Char(' ') => app.toggle_task(),
-                KeyCode::Char('a') | KeyCode::Char('A') => {
-                    app.mode = AppMode::AddTask;
-                    app.input_buffer.clear();
-                }
-                KeyCode::Char('e') | KeyCode::Char('E') => {
-                    if !app.tasks.is_empty() {
-                        app.mode = AppMode::EditTask;
-                        app.input_buffer = app.tasks[app.selected_index].title.clone();
-                    }
+                KeyCode::Backspace => {
+                    app.input_buffer.pop();
+                    app.refresh_filter();
                 }
-                KeyCode::Char('d') | KeyCode::Char('D') => {
-                    if !app.tasks.is_empty() {
-                        app.mode = AppMode::EditDescription;
-                        app.input_buffer = app.tasks[app.selected_index].description.clone();
+                KeyCode::Char(c) => {
+                    if modifiers.contains(KeyModifiers::CONTROL) && c == 'c' {
+                        return Ok(false);
                     }
+                    app.input_buffer.push(c);
+                    app.refresh_filter();
                 }
-                KeyCode::Delete => app.delete_task(),
                 _ => {}
             },
-            AppMode::AddTask | AppMode::EditTask | AppMode::AddDescription | AppMode::EditDescription => {
-                match code {
-                    KeyCode::Esc => {
-                        app.mode = AppMode::Normal;
-                        app.input_buffer.clear();
-                    }
-                    KeyCode::Enter => {
-                        let input = app.input_buffer.trim().to_string();
-                        if !input.is_empty() {
-                            match app.mode {
-                                AppMode::AddTask => {
-                                    app.mode = AppMode::AddDescription;
-                                    // Store the title temporarily
-                                    let title = app.input_buffer.clone();
-                                    app.input_buffer.clear();
-                                    // We'll need to store this title somewhere temporary
-                                    // For now, let's add the task with empty description
-                                    app.add_task(title, String::new());
-                                    app.mode = AppMode::Normal;
-                                }
-                                AppMode::EditTask => {
-                                    app.edit_current_task(input);
-                                    app.mode = AppMode::Normal;
-                                }
-                                AppMode::AddDescription => {
-                                    // This case won't happen with current flow
-                                    app.mode = AppMode::Normal;
-                                }
-                                AppMode::EditDescription => {
-                                    app.edit_current_description(input);
-                                    app.mode = AppMode::Normal;
-                                }
-                                _ => {}
+            AppMode::AddTask | AppMode::EditTask => match code {
+                KeyCode::Esc => {
+                    app.mode = AppMode::Normal;
+                    app.input_buffer.clear();
+                }
+                KeyCode::Enter => {
+                    let input = app.input_buffer.trim().to_string();
+                    app.input_buffer.clear();
+                    if !input.is_empty() {
+                        match app.mode {
+                            AppMode::AddTask => {
+                                app.mode = AppMode::Normal;
+                                let description = app.edit_in_editor("")?.unwrap_or_default();
+                                app.add_task(input, description);
                             }
-                        } else {
-                            app.mode = AppMode::Normal;
+                            AppMode::EditTask => {
+                                app.mode = AppMode::Normal;
+                                return Ok(app.handle_msg(Msg::EditTitle(input)));
+                            }
+                            _ => {}
                         }
-                        app.input_buffer.clear();
-                    }
-                    KeyCode::Backspace => {
-                        app.input_buffer.pop();
+                    } else {
+                        app.mode = AppMode::Normal;
                     }
-                    KeyCode::Char(c) => {
-                        if modifiers.contains(KeyModifiers::CONTROL) {
-                            match c {
-                                'c' => return Ok(false),
-                                _ => {}
-                            }
-                        } else {
-                            app.input_buffer.push(c);
+                }
+                KeyCode::Backspace => {
+                    app.input_buffer.pop();
+                }
+                KeyCode::Char(c) => {
+                    if modifiers.contains(KeyModifiers::CONTROL) {
+                        if c == 'c' {
+                            return Ok(false);
                         }
+                    } else {
+                        app.input_buffer.push(c);
                     }
-                    _ => {}
                 }
-            }
+                _ => {}
+            },
         }
     }
     Ok(true)
 }
 
+// Parses one scripted command line (as passed to `--exec` or piped on
+// stdin) into the `Msg` it names, e.g. `DeleteTask` or `AddTask(Buy milk)`.
+fn parse_msg(line: &str) -> Option<Msg> {
+    let line = line.trim();
+    if let Some(arg) = line.strip_prefix("AddTask(").and_then(|s| s.strip_suffix(')')) {
+        return Some(Msg::AddTask(arg.to_string()));
+    }
+    if let Some(arg) = line.strip_prefix("EditTitle(").and_then(|s| s.strip_suffix(')')) {
+        return Some(Msg::EditTitle(arg.to_string()));
+    }
+    if let Some(arg) = line
+        .strip_prefix("EditDescription(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        return Some(Msg::EditDescription(arg.to_string()));
+    }
+    match line {
+        "FocusNext" => Some(Msg::FocusNext),
+        "FocusPrevious" => Some(Msg::FocusPrevious),
+        "ToggleStatus" => Some(Msg::ToggleStatus),
+        "StartTask" => Some(Msg::StartTask),
+        "StopTask" => Some(Msg::StopTask),
+        "InboxTask" => Some(Msg::InboxTask),
+        "Undo" => Some(Msg::Undo),
+        "DeleteTask" => Some(Msg::DeleteTask),
+        "Quit" => Some(Msg::Quit),
+        _ => None,
+    }
+}
+
 fn main() -> std::io::Result<()> {
     let matches = Command::new("rtasks")
         .version("0.1.0")
@@ -388,10 +1105,31 @@ fn main() -> std::io::Result<()> {
                 .help("List all tasks and exit")
                 .action(clap::ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new("exec")
+                .long("exec")
+                .value_name("MSG")
+                .help("Run one or more ';'-separated Msg commands headlessly and exit \
+                       (e.g. --exec \"ToggleStatus;DeleteTask\"); more commands can be \
+                       piped on stdin, one per line")
+                .action(clap::ArgAction::Set)
+        )
+        .arg(
+            Arg::new("backend")
+                .long("backend")
+                .value_name("BACKEND")
+                .help("Storage backend to use: json (default) or taskwarrior")
+                .action(clap::ArgAction::Set)
+        )
         .get_matches();
 
+    let backend = match matches.get_one::<String>("backend").map(String::as_str) {
+        Some("taskwarrior") => Backend::Taskwarrior,
+        _ => Backend::Json,
+    };
+
     // Create app instance
-    let mut app = App::new();
+    let mut app = App::new(backend);
 
     // Handle command-line arguments
     if let Some(task_title) = matches.get_one::<String>("add") {
@@ -410,7 +1148,12 @@ fn main() -> std::io::Result<()> {
         } else {
             println!("ðŸ“‹ Your tasks:");
             for task in &app.tasks {
-                let status = if task.completed { "âœ…" } else { "â¬œ" };
+                let status = match task.status {
+                    TaskStatus::Inbox => "ðŸ“¥",
+                    TaskStatus::Pending => "â¬œ",
+                    TaskStatus::Active => "ðŸ”¶",
+                    TaskStatus::Done => "âœ…",
+                };
                 let desc = if task.description.is_empty() {
                     String::new()
                 } else {
@@ -422,6 +1165,32 @@ fn main() -> std::io::Result<()> {
         return Ok(());
     }
 
+    if let Some(exec_arg) = matches.get_one::<String>("exec") {
+        let mut run_lines = |lines: Vec<String>| {
+            for line in lines {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match parse_msg(line) {
+                    Some(msg) => {
+                        if !app.handle_msg(msg) {
+                            break;
+                        }
+                    }
+                    None => eprintln!("Unknown command: {}", line),
+                }
+            }
+        };
+
+        run_lines(exec_arg.split(';').map(|s| s.to_string()).collect());
+        if !stdin().is_terminal() {
+            run_lines(stdin().lines().map_while(Result::ok).collect());
+        }
+
+        return Ok(());
+    }
+
     // Show data location on first run
     let data_file = App::get_data_file_path();
     if !data_file.exists() {
@@ -449,3 +1218,100 @@ fn main() -> std::io::Result<()> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Records nothing anywhere; lets tests exercise `handle_msg` purely
+    // in-memory without touching the filesystem or shelling out to `task`.
+    #[derive(Debug)]
+    struct NullStore;
+
+    impl Store for NullStore {
+        fn load(&self) -> Vec<Task> {
+            Vec::new()
+        }
+        fn save(&self, _tasks: &[Task]) {}
+        fn add(&self, _task: &mut Task) {}
+        fn update(&self, _task: &Task) {}
+        fn delete(&self, _task: &Task) {}
+    }
+
+    fn test_app() -> App {
+        App {
+            tasks: Vec::new(),
+            selected_index: 0,
+            mode: AppMode::Normal,
+            input_buffer: String::new(),
+            next_id: 1,
+            operations: Vec::new(),
+            status_message: None,
+            visible: Vec::new(),
+            filter_active: false,
+            store: Box::new(NullStore),
+        }
+    }
+
+    #[test]
+    fn add_task_appends_and_advances_next_id() {
+        let mut app = test_app();
+        app.handle_msg(Msg::AddTask("write docs".to_string()));
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].title, "write docs");
+        assert_eq!(app.next_id, 2);
+    }
+
+    #[test]
+    fn toggle_status_flips_done_and_back() {
+        let mut app = test_app();
+        app.handle_msg(Msg::AddTask("task".to_string()));
+        app.handle_msg(Msg::ToggleStatus);
+        assert_eq!(app.tasks[0].status, TaskStatus::Done);
+        app.handle_msg(Msg::ToggleStatus);
+        assert_eq!(app.tasks[0].status, TaskStatus::Pending);
+    }
+
+    #[test]
+    fn quit_returns_false() {
+        let mut app = test_app();
+        assert!(!app.handle_msg(Msg::Quit));
+    }
+
+    #[test]
+    fn delete_then_undo_restores_the_task() {
+        let mut app = test_app();
+        app.handle_msg(Msg::AddTask("task".to_string()));
+        app.handle_msg(Msg::DeleteTask);
+        assert!(app.tasks.is_empty());
+        app.handle_msg(Msg::Undo);
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].title, "task");
+    }
+
+    #[test]
+    fn fuzzy_score_empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("anything", ""), Some(0));
+    }
+
+    // `fuzzy_score` itself is case-sensitive; callers fold case (see
+    // `refresh_filter`) before scoring, so exercise that contract here.
+    #[test]
+    fn fuzzy_score_matches_after_case_folding() {
+        let haystack = "Write Docs".to_lowercase();
+        assert!(fuzzy_score(&haystack, "docs").is_some());
+    }
+
+    #[test]
+    fn fuzzy_score_rejects_out_of_order_or_missing_chars() {
+        assert_eq!(fuzzy_score("docs", "xyz"), None);
+        assert_eq!(fuzzy_score("abc", "cab"), None);
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_consecutive_runs_over_scattered_matches() {
+        let consecutive = fuzzy_score("docs", "do").unwrap();
+        let scattered = fuzzy_score("d_o", "do").unwrap();
+        assert!(consecutive > scattered);
+    }
+}